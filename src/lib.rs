@@ -30,7 +30,7 @@
 //! In addition to `as_num`, it offers a method `checked_as_num`, returning an `Option`.
 //!
 //! This module implements conversion for any combination of the following types:
-//! `i8`, `i16`, `i32`, `i64`, `isize`, `u8`, `u16`, `u32`, `u64`, `usize`, `f32`, `f64`.
+//! `i8`, `i16`, `i32`, `i64`, `i128`, `isize`, `u8`, `u16`, `u32`, `u64`, `u128`, `usize`, `f32`, `f64`.
 //!
 //! The function `as_num` `debug_assert`s that the destination value is convertible back to the
 //! exact same source value.
@@ -43,9 +43,8 @@ use std::fmt::Debug;
 
 // heavily inspired by http://rust-num.github.io/num/src/num_traits/cast.rs.html
 
-// TODO rust i128/u128
-type LargestSignedType = i64;
-type LargestUnsignedType = u64;
+type LargestSignedType = i128;
+type LargestUnsignedType = u128;
 
 pub trait SignedInt : Sized + Copy {
     #[inline(always)]
@@ -80,14 +79,28 @@ macro_rules! impl_min_max {
     };
 }
 
-impl_min_max!(SignedInt, LargestSignedType, i8, i16, i32, i64, isize,);
-impl_min_max!(UnsignedInt, LargestUnsignedType, u8, u16, u32, u64, usize,);
+impl_min_max!(SignedInt, LargestSignedType, i8, i16, i32, i64, i128, isize,);
+impl_min_max!(UnsignedInt, LargestUnsignedType, u8, u16, u32, u64, u128, usize,);
 
 pub trait AsNumInternal<Dest> : Copy {
     #[inline(always)]
     fn is_safely_convertible(self) -> bool;
     #[inline(always)]
     fn as_num_internal(self) -> Dest;
+    /// Convert to `Dest`, clamping to the nearest representable `Dest` value instead of losing
+    /// the number's meaning when it falls outside `Dest`'s range. For float->int this mirrors
+    /// Rust's own saturating `as` (out-of-range saturates to the bound, `NaN` maps to `0`).
+    #[inline(always)]
+    fn saturating_as_num_internal(self) -> Dest;
+    /// Convert to `Dest` with no range or round-trip check at all. For float->int this lowers to
+    /// `to_int_unchecked`.
+    ///
+    /// # Safety
+    /// The caller must have already proven that `self` is representable in `Dest`. For float->int
+    /// destinations the value must additionally be finite and within `Dest`'s range; otherwise the
+    /// behaviour is undefined, exactly like `to_int_unchecked`.
+    #[inline(always)]
+    unsafe fn as_num_internal_unchecked(self) -> Dest;
 }
 
 pub trait AsNum {
@@ -102,6 +115,20 @@ pub trait AsNum {
               Dest: AsNumInternal<Self>,
               Dest: Debug;
     #[inline(always)]
+    fn saturating_as_num<Dest>(self) -> Dest
+        where Self: AsNumInternal<Dest>;
+    /// Convert to `Dest` going straight to the raw cast (`as`, or `to_int_unchecked` for
+    /// float->int), skipping both `is_safely_convertible`
+    /// and the `assert_convertible_back` round-trip. Intended for hot paths where the caller has
+    /// externally proven the value is in range.
+    ///
+    /// # Safety
+    /// See [`AsNumInternal::as_num_internal_unchecked`]: the caller is responsible for the value
+    /// being representable in `Dest` (and finite/in-range for float->int destinations).
+    #[inline(always)]
+    unsafe fn as_num_unchecked<Dest>(self) -> Dest
+        where Self: AsNumInternal<Dest>;
+    #[inline(always)]
     fn assert_convertible_back<Dest>(self)
         where Self: AsNumInternal<Dest>,
               Dest: AsNumInternal<Self>,
@@ -145,13 +172,25 @@ macro_rules! impl_TAsNum {
                     None
                 }
             }
+            #[inline(always)]
+            fn saturating_as_num<Dest>(self) -> Dest
+                where Self: AsNumInternal<Dest>,
+            {
+                self.saturating_as_num_internal()
+            }
+            #[inline(always)]
+            unsafe fn as_num_unchecked<Dest>(self) -> Dest
+                where Self: AsNumInternal<Dest>,
+            {
+                self.as_num_internal_unchecked()
+            }
         }
         impl_TAsNum!($($ts,)*);
     };
 }
 impl_TAsNum!(
-    i8, i16, i32, i64, isize,
-    u8, u16, u32, u64, usize,
+    i8, i16, i32, i64, i128, isize,
+    u8, u16, u32, u64, u128, usize,
     f32, f64,
 );
 
@@ -171,6 +210,21 @@ macro_rules! impl_signed_to_signed_internal {
             fn as_num_internal(self) -> $dest {
                 self as $dest
             }
+            #[inline(always)]
+            fn saturating_as_num_internal(self) -> $dest {
+                let n = self as LargestSignedType;
+                if n < <$dest as SignedInt>::min() {
+                    <$dest as SignedInt>::min() as $dest
+                } else if n > <$dest as SignedInt>::max() {
+                    <$dest as SignedInt>::max() as $dest
+                } else {
+                    self as $dest
+                }
+            }
+            #[inline(always)]
+            unsafe fn as_num_internal_unchecked(self) -> $dest {
+                self as $dest
+            }
         }
     };
 }
@@ -195,6 +249,20 @@ macro_rules! impl_signed_to_unsigned_internal {
             fn as_num_internal(self) -> $dest {
                 self as $dest
             }
+            #[inline(always)]
+            fn saturating_as_num_internal(self) -> $dest {
+                if self < 0 {
+                    <$dest as UnsignedInt>::min() as $dest
+                } else if self as LargestUnsignedType > <$dest as UnsignedInt>::max() {
+                    <$dest as UnsignedInt>::max() as $dest
+                } else {
+                    self as $dest
+                }
+            }
+            #[inline(always)]
+            unsafe fn as_num_internal_unchecked(self) -> $dest {
+                self as $dest
+            }
         }
     }
 }
@@ -219,6 +287,18 @@ macro_rules! impl_unsigned_to_signed_internal {
             fn as_num_internal(self) -> $dest {
                 self as $dest
             }
+            #[inline(always)]
+            fn saturating_as_num_internal(self) -> $dest {
+                if self as LargestUnsignedType > <$dest as SignedInt>::max() as LargestUnsignedType {
+                    <$dest as SignedInt>::max() as $dest
+                } else {
+                    self as $dest
+                }
+            }
+            #[inline(always)]
+            unsafe fn as_num_internal_unchecked(self) -> $dest {
+                self as $dest
+            }
         }
     };
 }
@@ -244,6 +324,18 @@ macro_rules! impl_unsigned_to_unsigned_internal {
             fn as_num_internal(self) -> $dest {
                 self as $dest
             }
+            #[inline(always)]
+            fn saturating_as_num_internal(self) -> $dest {
+                if self as LargestUnsignedType > <$dest as UnsignedInt>::max() {
+                    <$dest as UnsignedInt>::max() as $dest
+                } else {
+                    self as $dest
+                }
+            }
+            #[inline(always)]
+            unsafe fn as_num_internal_unchecked(self) -> $dest {
+                self as $dest
+            }
         }
     };
 }
@@ -273,8 +365,8 @@ macro_rules! impl_integral_conversions {
 }
 
 impl_integral_conversions!(
-    (i8, i16, i32, i64, isize,),
-    (u8, u16, u32, u64, usize,)
+    (i8, i16, i32, i64, i128, isize,),
+    (u8, u16, u32, u64, u128, usize,)
 );
 
 macro_rules! impl_integral_to_float_internal {
@@ -283,12 +375,34 @@ macro_rules! impl_integral_to_float_internal {
         impl AsNumInternal<$flt> for $int {
             #[inline(always)]
             fn is_safely_convertible(self) -> bool {
-                true // assume convertability until we encounter counter example in practice
+                // Wide integers (e.g. u128/i128) can exceed the float's mantissa and lose
+                // precision. A bare round-trip is not enough: the float->int back-cast saturates
+                // at the destination integer's maximum, so a value like `u128::MAX` rounds *up* to
+                // the saturation boundary and comes back unchanged, hiding the loss. Keep the
+                // round-trip, but additionally reject any value whose float reaches that boundary.
+                let dst : $flt = self.as_num_internal();
+                let src : Self = dst.as_num_internal();
+                let bits = (mem::size_of::<$int>() * 8) as i32;
+                let saturation_threshold = if (<$int>::MIN as i128) < 0 {
+                    $flt::from(2u8).powi(bits - 1)
+                } else {
+                    $flt::from(2u8).powi(bits)
+                };
+                self==src && dst < saturation_threshold
             }
             #[inline(always)]
             fn as_num_internal(self) -> $flt {
                 self as $flt
             }
+            #[inline(always)]
+            fn saturating_as_num_internal(self) -> $flt {
+                // `as` already yields the nearest representable float.
+                self as $flt
+            }
+            #[inline(always)]
+            unsafe fn as_num_internal_unchecked(self) -> $flt {
+                self as $flt
+            }
         }
         impl AsNumInternal<$int> for $flt {
             #[inline(always)]
@@ -301,6 +415,15 @@ macro_rules! impl_integral_to_float_internal {
             fn as_num_internal(self) -> $int {
                 self as $int
             }
+            #[inline(always)]
+            fn saturating_as_num_internal(self) -> $int {
+                // Rust's float->int `as` already saturates to the bounds and maps NaN to 0.
+                self as $int
+            }
+            #[inline(always)]
+            unsafe fn as_num_internal_unchecked(self) -> $int {
+                self.to_int_unchecked::<$int>()
+            }
         }
         impl_integral_to_float_internal!($flt, $($ints,)*);
     };
@@ -308,8 +431,8 @@ macro_rules! impl_integral_to_float_internal {
 macro_rules! impl_integral_to_float {
     ($flt: ident) => {
         impl_integral_to_float_internal!($flt,
-            i8, i16, i32, i64, isize,
-            u8, u16, u32, u64, usize,
+            i8, i16, i32, i64, i128, isize,
+            u8, u16, u32, u64, u128, usize,
         );
     };
 }
@@ -337,6 +460,15 @@ macro_rules! impl_float_to_float_internal {
             fn as_num_internal(self) -> $dest {
                 self as $dest
             }
+            #[inline(always)]
+            fn saturating_as_num_internal(self) -> $dest {
+                // Widening is exact; narrowing `as` saturates to +-inf.
+                self as $dest
+            }
+            #[inline(always)]
+            unsafe fn as_num_internal_unchecked(self) -> $dest {
+                self as $dest
+            }
         }
     }
 }
@@ -350,6 +482,140 @@ macro_rules! impl_float_to_float {
 }
 impl_float_to_float!(f32, f64,);
 
+/// For many conversions losslessness is guaranteed purely by the types involved (e.g. `u8`->`u32`,
+/// `i16`->`i64`, any unsigned into a strictly wider signed type). There is no point paying for the
+/// `debug_assert` round-trip of `as_num` or unwrapping the `Option` of `checked_as_num` in those
+/// cases, so `WideningAsNum` offers `widen`, which returns `Dest` directly without any runtime
+/// check. The `WidenInto` bound itself is the proof: narrowing pairs simply do not implement it, so
+/// `widen` only compiles where data genuinely cannot be lost.
+///
+/// `isize`/`usize` do not participate, because their width is not known at compile time and thus no
+/// widening involving them can be statically guaranteed across targets.
+pub trait WidenInto<Dest> : Copy {
+    #[inline(always)]
+    fn widen_internal(self) -> Dest;
+}
+
+pub trait WideningAsNum {
+    #[inline(always)]
+    fn widen<Dest>(self) -> Dest
+        where Self: WidenInto<Dest>;
+}
+
+macro_rules! impl_widen_into {
+    ($src: ident, $dest: ident) => {
+        impl WidenInto<$dest> for $src {
+            #[inline(always)]
+            fn widen_internal(self) -> $dest {
+                self as $dest
+            }
+        }
+    };
+}
+
+// Within one signedness (and for floats) every type widens losslessly into itself and any wider type.
+macro_rules! impl_widen_chain {
+    () => {};
+    ($src: ident, $($wider: ident,)*) => {
+        impl_widen_into!($src, $src);
+        $( impl_widen_into!($src, $wider); )*
+        impl_widen_chain!($($wider,)*);
+    };
+}
+impl_widen_chain!(i8, i16, i32, i64, i128,);
+impl_widen_chain!(u8, u16, u32, u64, u128,);
+impl_widen_chain!(f32, f64,);
+
+// An unsigned type widens losslessly into every *strictly* wider signed type (same width is lossy
+// because the unsigned maximum exceeds the signed maximum).
+macro_rules! impl_widen_unsigned_to_signed {
+    ((), ()) => {};
+    (($u: ident, $($us: ident,)*), ($i: ident, $($is: ident,)*)) => {
+        $( impl_widen_into!($u, $is); )*
+        impl_widen_unsigned_to_signed!(($($us,)*), ($($is,)*));
+    };
+}
+impl_widen_unsigned_to_signed!(
+    (u8, u16, u32, u64, u128,),
+    (i8, i16, i32, i64, i128,)
+);
+
+macro_rules! impl_widening_as_num {
+    () => {};
+    ($t: ident, $($ts: ident,)*) => {
+        impl WideningAsNum for $t {
+            #[inline(always)]
+            fn widen<Dest>(self) -> Dest
+                where Self: WidenInto<Dest>,
+            {
+                self.widen_internal()
+            }
+        }
+        impl_widening_as_num!($($ts,)*);
+    };
+}
+impl_widening_as_num!(
+    i8, i16, i32, i64, i128,
+    u8, u16, u32, u64, u128,
+    f32, f64,
+);
+
+/// How a float should be rounded to an integral value before converting it to an integer.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum RoundingMode {
+    /// Round towards zero (`f32::trunc`/`f64::trunc`).
+    Trunc,
+    /// Round towards negative infinity (`floor`).
+    Floor,
+    /// Round towards positive infinity (`ceil`).
+    Ceil,
+    /// Round to the nearest integer, ties away from zero (`round`).
+    Nearest,
+}
+
+/// `as_num` only admits a float->int conversion when the float is already integral. `round_as_num`
+/// lets you opt into the precision loss explicitly: it first rounds the float to an integral value
+/// according to `mode`, then performs the usual range-checked conversion. The result is `None`
+/// exactly when the rounded value does not fit the destination integer, or the input is `NaN`/`inf`.
+pub trait RoundAsNum {
+    #[inline(always)]
+    fn round_as_num<Dest>(self, mode: RoundingMode) -> Option<Dest>
+        where Self: AsNumInternal<Dest>,
+              Dest: AsNumInternal<Self>,
+              Dest: Debug;
+}
+
+macro_rules! impl_round_as_num {
+    () => {};
+    ($flt: ident, $($flts: ident,)*) => {
+        impl RoundAsNum for $flt {
+            #[inline(always)]
+            fn round_as_num<Dest>(self, mode: RoundingMode) -> Option<Dest>
+                where Self: AsNumInternal<Dest>,
+                      Dest: AsNumInternal<Self>,
+                      Dest: Debug,
+            {
+                if !self.is_finite() {
+                    return None;
+                }
+                let rounded = match mode {
+                    RoundingMode::Trunc => self.trunc(),
+                    RoundingMode::Floor => self.floor(),
+                    RoundingMode::Ceil => self.ceil(),
+                    RoundingMode::Nearest => self.round(),
+                };
+                // The float->int round-trip alone accepts a rounded value at the saturation
+                // boundary (e.g. 2^31 as f32 saturates to i32::MAX, which casts back to 2^31), so
+                // also require the result to convert back into `Self` without reaching that boundary.
+                rounded.checked_as_num::<Dest>()
+                    .filter(|dst| <Dest as AsNumInternal<Self>>::is_safely_convertible(*dst))
+            }
+        }
+        impl_round_as_num!($($flts,)*);
+    };
+}
+impl_round_as_num!(f32, f64,);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -364,6 +630,59 @@ mod tests {
         assert_eq!(4.3.checked_as_num::<isize>(), None);
     }
 
+    #[test]
+    fn test_saturating_as_num() {
+        assert_eq!(255u8, 300i32.saturating_as_num::<u8>());
+        assert_eq!(0u8, (-5i32).saturating_as_num::<u8>());
+        assert_eq!(127i8, 1000u32.saturating_as_num::<i8>());
+        assert_eq!(4u8, 4i32.saturating_as_num::<u8>());
+        assert_eq!(0i32, (f64::NAN).saturating_as_num::<i32>());
+        assert_eq!(i32::MAX, 1.0e30f64.saturating_as_num::<i32>());
+    }
+
+    #[test]
+    fn test_widen() {
+        assert_eq!(42u32, 42u8.widen());
+        assert_eq!(-7i64, (-7i16).widen());
+        let i: i64 = 200u8.widen();
+        assert_eq!(200i64, i);
+        assert_eq!(1.5f64, 1.5f32.widen());
+    }
+
+    #[test]
+    fn test_round_as_num() {
+        assert_eq!(Some(4i32), 4.7f64.round_as_num(RoundingMode::Trunc));
+        assert_eq!(Some(4i32), 4.7f64.round_as_num(RoundingMode::Floor));
+        assert_eq!(Some(5i32), 4.2f64.round_as_num(RoundingMode::Ceil));
+        assert_eq!(Some(5i32), 4.5f64.round_as_num(RoundingMode::Nearest));
+        assert_eq!(Some(-5i32), (-4.7f64).round_as_num(RoundingMode::Floor));
+        assert_eq!(None, 1.0e30f64.round_as_num::<i32>(RoundingMode::Trunc));
+        assert_eq!(None, f64::NAN.round_as_num::<i32>(RoundingMode::Nearest));
+        assert_eq!(None, (-1.0f64).round_as_num::<u8>(RoundingMode::Trunc));
+    }
+
+    #[test]
+    fn test_as_num_unchecked() {
+        assert_eq!(4i8, unsafe { 4i32.as_num_unchecked::<i8>() });
+        assert_eq!(42u64, unsafe { 42u8.as_num_unchecked::<u64>() });
+        assert_eq!(7i32, unsafe { 7.9f64.as_num_unchecked::<i32>() });
+    }
+
+    #[test]
+    fn test_wide_int_to_float_precision() {
+        // The top edge must be rejected: these all lose precision even though the
+        // saturating back-cast would reproduce the maximum.
+        assert_eq!(None, u128::MAX.checked_as_num::<f64>());
+        assert_eq!(None, u64::MAX.checked_as_num::<f32>());
+        assert_eq!(None, i128::MAX.checked_as_num::<f64>());
+        assert_eq!(None, u128::MAX.checked_as_num::<f32>());
+        // Exactly representable values (including the signed minimum, a power of two)
+        // still convert.
+        assert_eq!(Some(0u128.as_num::<f64>()), u128::MIN.checked_as_num::<f64>());
+        assert_eq!(Some((1u64 << 40) as f64), (1u64 << 40).checked_as_num::<f64>());
+        assert_eq!(Some(i64::MIN as f64), i64::MIN.checked_as_num::<f64>());
+    }
+
     #[test]
     fn test_ulargest_to_ilargest() {
         assert_eq!(